@@ -0,0 +1,234 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Fields, Token, Variant};
+
+use super::feature::{EnumFeatures, FromAttributes};
+use super::{insert_schema_component, DiagResultExt, NamedStructSchema, UnnamedStructSchema, XmlAttr};
+use crate::feature::{
+    pop_feature_as_inner, Bound, Feature, FeaturesExt, Inline, IntoInner, NameSeparator, RenameAll, SkipBound, Symbol,
+};
+use crate::{DiagResult, Diagnostic, TryToTokens, VariantRename};
+
+#[derive(Debug)]
+pub(crate) struct EnumSchema<'a> {
+    enum_name: Cow<'a, str>,
+    variants: &'a Punctuated<Variant, Token![,]>,
+    attributes: &'a [Attribute],
+    features: Vec<Feature>,
+    pub(super) symbol: Option<Symbol>,
+    pub(super) inline: Option<Inline>,
+    pub(super) xml: Option<XmlAttr>,
+    name_separator: Option<NameSeparator>,
+    rename_all: Option<RenameAll>,
+}
+
+impl<'a> EnumSchema<'a> {
+    pub(crate) fn new(
+        enum_name: Cow<'a, str>,
+        variants: &'a Punctuated<Variant, Token![,]>,
+        attributes: &'a [Attribute],
+    ) -> DiagResult<Self> {
+        let mut features = attributes.parse_features::<EnumFeatures>()?.into_inner();
+        let symbol = pop_feature_as_inner!(features => Feature::Symbol(_v));
+        let inline = pop_feature_as_inner!(features => Feature::Inline(_v));
+        let xml = pop_feature_as_inner!(features => Feature::Xml(_v));
+        let name_separator = pop_feature_as_inner!(features => Feature::NameSeparator(_v));
+        let rename_all = features.pop_rename_all_feature();
+
+        Ok(Self {
+            enum_name,
+            variants,
+            attributes,
+            features,
+            symbol,
+            inline,
+            xml,
+            name_separator,
+            rename_all,
+        })
+    }
+
+    pub(super) fn pop_skip_bound(&mut self) -> Option<SkipBound> {
+        let index = self.features.iter().position(|feature| matches!(feature, Feature::SkipBound(_)))?;
+        match self.features.remove(index) {
+            Feature::SkipBound(skip_bound) => Some(skip_bound),
+            _ => unreachable!(),
+        }
+    }
+
+    pub(super) fn pop_bound(&mut self) -> Option<Bound> {
+        let index = self.features.iter().position(|feature| matches!(feature, Feature::Bound(_)))?;
+        match self.features.remove(index) {
+            Feature::Bound(bound) => Some(bound),
+            _ => unreachable!(),
+        }
+    }
+
+    pub(super) fn pop_generic_name_separator(&mut self) -> Option<NameSeparator> {
+        self.name_separator.take()
+    }
+
+    /// Registers `variant`'s schema as its own named component (reusing
+    /// [`super::insert_schema_component`]) and returns the `$ref` item tokens plus the
+    /// `(tag value, ref path)` pair needed for the discriminator `mapping`. The tag value
+    /// goes through [`VariantRename`] so it matches the container's `rename`/`rename_all`.
+    fn tagged_variant_component(
+        &self,
+        variant: &Variant,
+        tag: &str,
+        content: Option<&str>,
+        errors: &mut Option<Diagnostic>,
+    ) -> Option<(TokenStream, String, String)> {
+        let payload = self.variant_schema(variant, errors)?;
+        let oapi = crate::oapi_crate();
+        // Internally tagged: `allOf`-compose the tag with the payload rather than chaining
+        // `.property()` onto it directly, since `payload` may be a `RefOr<Schema>` (newtype
+        // variants), not always an `Object`.
+        let tagged_schema = match content {
+            Some(content_name) => quote! {
+                #oapi::oapi::schema::Object::new()
+                    .property(#tag, #oapi::oapi::schema::Object::with_type(#oapi::oapi::schema::SchemaType::String))
+                    .property(#content_name, #payload)
+            },
+            None => quote! {
+                #oapi::oapi::schema::AllOf::new()
+                    .item(#oapi::oapi::schema::Object::new()
+                        .property(#tag, #oapi::oapi::schema::Object::with_type(#oapi::oapi::schema::SchemaType::String)))
+                    .item(#payload)
+            },
+        };
+
+        let tag_value = variant
+            .rename(self.rename_all.as_ref())
+            .unwrap_or_else(|| variant.ident.to_string());
+        let symbol = format!("{}{}", self.enum_name, variant.ident);
+        let ref_path = format!("#/components/schemas/{symbol}");
+        let symbol_tokens = quote! { #symbol };
+        let component = insert_schema_component(&symbol_tokens, &tagged_schema);
+
+        Some((quote! { { #component } }, tag_value, ref_path))
+    }
+
+    /// Builds the schema for a single variant's payload by delegating to the same struct
+    /// schema builders used for top-level structs, keyed by the variant's field shape.
+    fn variant_schema(&self, variant: &Variant, errors: &mut Option<Diagnostic>) -> Option<TokenStream> {
+        match &variant.fields {
+            Fields::Named(fields) => NamedStructSchema {
+                struct_name: Cow::Owned(variant.ident.to_string()),
+                attributes: &variant.attrs,
+                rename_all: None,
+                features: Vec::new(),
+                fields: &fields.named,
+                generics: None,
+                symbol: None,
+                inline: None,
+                xml: None,
+                name_separator: None,
+            }
+            .try_to_token_stream()
+            .accumulate(errors),
+            Fields::Unnamed(fields) => UnnamedStructSchema {
+                struct_name: Cow::Owned(variant.ident.to_string()),
+                attributes: &variant.attrs,
+                features: Vec::new(),
+                fields: &fields.unnamed,
+                symbol: None,
+                inline: None,
+                xml: None,
+                name_separator: None,
+            }
+            .try_to_token_stream()
+            .accumulate(errors),
+            Fields::Unit => {
+                let oapi = crate::oapi_crate();
+                Some(quote! { #oapi::oapi::schema::empty() })
+            }
+        }
+    }
+}
+
+impl TryToTokens for EnumSchema<'_> {
+    fn try_to_tokens(&self, tokens: &mut TokenStream) -> DiagResult<()> {
+        let oapi = crate::oapi_crate();
+        let mut errors = None;
+        let (tag, content) = serde_tag_content(self.attributes);
+
+        let schema = match tag {
+            Some(tag) => {
+                let mut items = Vec::new();
+                let mut mapping_values = Vec::new();
+                let mut mapping_refs = Vec::new();
+                for variant in self.variants {
+                    let Some((item, tag_value, ref_path)) =
+                        self.tagged_variant_component(variant, &tag, content.as_deref(), &mut errors)
+                    else {
+                        continue;
+                    };
+                    items.push(item);
+                    mapping_values.push(tag_value);
+                    mapping_refs.push(ref_path);
+                }
+
+                if let Some(errors) = errors {
+                    return Err(errors);
+                }
+
+                quote! {
+                    #oapi::oapi::schema::OneOf::new()
+                        #(.item(#items))*
+                        .discriminator(
+                            #oapi::oapi::schema::Discriminator::new(#tag)
+                                #(.mapping(#mapping_values, #mapping_refs))*
+                        )
+                }
+            }
+            None => {
+                let mut items = Vec::new();
+                for variant in self.variants {
+                    let Some(item) = self.variant_schema(variant, &mut errors) else {
+                        continue;
+                    };
+                    items.push(item);
+                }
+
+                if let Some(errors) = errors {
+                    return Err(errors);
+                }
+
+                quote! {
+                    #oapi::oapi::schema::OneOf::new()
+                        #(.item(#items))*
+                }
+            }
+        };
+
+        tokens.extend(schema);
+        Ok(())
+    }
+}
+
+/// Scans the enum's own `#[serde(...)]` attributes for internal (`tag = "..."`) or adjacent
+/// (`tag = "...", content = "..."`) tagging, returning `(tag, content)`.
+fn serde_tag_content(attributes: &[Attribute]) -> (Option<String>, Option<String>) {
+    let mut tag = None;
+    let mut content = None;
+    for attr in attributes {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                tag = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.path.is_ident("content") {
+                content = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            } else if meta.input.peek(syn::Token![=]) {
+                let _ = meta.value()?.parse::<TokenStream>();
+            }
+            Ok(())
+        });
+    }
+    (tag, content)
+}
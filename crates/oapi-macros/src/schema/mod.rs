@@ -23,7 +23,7 @@ use super::{
     feature::{pop_feature_as_inner, Feature, FeaturesExt, IntoInner},
     ComponentSchema, FieldRename, VariantRename,
 };
-use crate::feature::{Bound, Inline, SkipBound, Symbol};
+use crate::feature::{Bound, Inline, NameSeparator, SkipBound, Symbol};
 use crate::serde_util::SerdeValue;
 use crate::{bound, DiagLevel, DiagResult, Diagnostic, TryToTokens};
 
@@ -53,28 +53,140 @@ impl<'a> ToSchema<'a> {
     }
 }
 
+/// Accumulates [`Diagnostic`]s across independent fallible steps instead of bailing out with
+/// `?` on the first one. `NamedStructSchema`, `UnnamedStructSchema` and `EnumSchema` use this
+/// to keep parsing every field/variant and merge whatever diagnostics they collect via
+/// `Diagnostic::extend`, so a struct with three malformed attributes is reported all at once
+/// rather than one edit-compile-edit cycle at a time.
+pub(crate) trait DiagResultExt<T> {
+    /// Folds `self` into `errors`, returning the success value unless this step failed.
+    fn accumulate(self, errors: &mut Option<Diagnostic>) -> Option<T>;
+}
+
+impl<T> DiagResultExt<T> for DiagResult<T> {
+    fn accumulate(self, errors: &mut Option<Diagnostic>) -> Option<T> {
+        match self {
+            Ok(value) => Some(value),
+            Err(diagnostic) => {
+                *errors = Some(match errors.take() {
+                    Some(existing) => existing.extend(diagnostic),
+                    None => diagnostic,
+                });
+                None
+            }
+        }
+    }
+}
+
 impl TryToTokens for ToSchema<'_> {
     fn try_to_tokens(&self, tokens: &mut TokenStream) -> DiagResult<()> {
         let oapi = crate::oapi_crate();
         let ident = self.ident;
-        let mut variant = SchemaVariant::new(self.data, self.attributes, ident, self.generics)?;
+        let mut errors = None;
+
+        let Some(mut variant) =
+            SchemaVariant::new(self.data, self.attributes, ident, self.generics).accumulate(&mut errors)
+        else {
+            return Err(errors.expect("a failed step always leaves a diagnostic behind"));
+        };
 
         let (_, ty_generics, _) = self.generics.split_for_impl();
 
+        let type_params: Vec<&Ident> = self.generics.type_params().map(|param| &param.ident).collect();
+
         let inline = variant.inline().as_ref().map(|i| i.0).unwrap_or(false);
+        let needs_separator = !inline && !type_params.is_empty() && variant.symbol().is_some();
+        let separator = needs_separator.then(|| {
+            variant
+                .pop_generic_name_separator()
+                .map(|s| s.0)
+                .unwrap_or_else(|| DEFAULT_GENERIC_NAME_SEPARATOR.to_string())
+        });
         let symbol = if inline {
             None
         } else if let Some(symbol) = variant.symbol() {
-            if self.generics.type_params().next().is_none() {
+            if type_params.is_empty() {
                 Some(quote! { #symbol.to_string().replace(" :: ", ".") })
             } else {
+                let separator = separator.expect("computed above whenever type_params is non-empty");
                 Some(quote! {
                    {
+                       // Ensure every concrete type argument's own schema is registered so the
+                       // `$ref` this component name is about to produce actually resolves.
+                       #(let _ = <#type_params as #oapi::oapi::ToSchema>::to_schema(components);)*
+
+                       fn __salvo_split_top_level_args(args: &str) -> ::std::vec::Vec<&str> {
+                           let mut parts = ::std::vec::Vec::new();
+                           let mut depth = 0i32;
+                           let mut start = 0usize;
+                           for (i, c) in args.char_indices() {
+                               match c {
+                                   '<' => depth += 1,
+                                   '>' => depth -= 1,
+                                   ',' if depth == 0 => {
+                                       parts.push(args[start..i].trim());
+                                       start = i + 1;
+                                   }
+                                   _ => {}
+                               }
+                           }
+                           parts.push(args[start..].trim());
+                           parts
+                       }
+
+                       fn __salvo_matching_angle_bracket(s: &str, open: usize) -> Option<usize> {
+                           let mut depth = 0i32;
+                           for (i, c) in s[open..].char_indices() {
+                               match c {
+                                   '<' => depth += 1,
+                                   '>' => {
+                                       depth -= 1;
+                                       if depth == 0 {
+                                           return Some(open + i);
+                                       }
+                                   }
+                                   _ => {}
+                               }
+                           }
+                           None
+                       }
+
+                       fn __salvo_sanitize_path(path: &str) -> String {
+                           path.trim()
+                               .replace("::", "_")
+                               .chars()
+                               .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                               .collect()
+                       }
+
+                       fn __salvo_sanitize_arg(arg: &str) -> String {
+                           match arg.find('<').and_then(|open| __salvo_matching_angle_bracket(arg, open).map(|close| (open, close))) {
+                               Some((open, close)) => {
+                                   let base = __salvo_sanitize_path(&arg[..open]);
+                                   let inner = __salvo_split_top_level_args(&arg[open + 1..close])
+                                       .into_iter()
+                                       .map(__salvo_sanitize_arg)
+                                       .collect::<::std::vec::Vec<_>>()
+                                       .join("_");
+                                   format!("{}_Of_{}", base, inner)
+                               }
+                               // Keep the full path (not just the last segment) so types that share a
+                               // short name in different modules/crates don't collide on one component name.
+                               None => __salvo_sanitize_path(arg),
+                           }
+                       }
+
                        let full_name = std::any::type_name::<#ident #ty_generics>();
-                       if let Some((_, args)) = full_name.split_once('<') {
-                           format!("{}<{}", #symbol, args)
-                       } else {
-                           full_name.into()
+                       match full_name.find('<').and_then(|open| __salvo_matching_angle_bracket(full_name, open).map(|close| (open, close))) {
+                           Some((open, close)) => {
+                               let sanitized = __salvo_split_top_level_args(&full_name[open + 1..close])
+                                   .into_iter()
+                                   .map(__salvo_sanitize_arg)
+                                   .collect::<::std::vec::Vec<_>>()
+                                   .join("_");
+                               format!("{}{}{}", #symbol, #separator, sanitized)
+                           }
+                           None => full_name.replace("::", "."),
                        }
                    }
                 })
@@ -100,20 +212,18 @@ impl TryToTokens for ToSchema<'_> {
 
         let (impl_generics, _, where_clause) = generics.split_for_impl();
 
-        let variant = variant.try_to_token_stream()?;
+        let container_xml = variant.xml().clone();
+        let Some(variant) = variant.try_to_token_stream().accumulate(&mut errors) else {
+            return Err(errors.expect("a failed step always leaves a diagnostic behind"));
+        };
+        let variant = wrap_schema_xml(variant, &container_xml);
         let body = match symbol {
             None => {
                 quote! {
                     #variant.into()
                 }
             }
-            Some(symbol) => {
-                quote! {
-                    let schema = #variant;
-                    components.schemas.insert(#symbol, schema.into());
-                    #oapi::oapi::RefOr::Ref(#oapi::oapi::Ref::new(format!("#/components/schemas/{}", #symbol)))
-                }
-            }
+            Some(symbol) => insert_schema_component(&symbol, &variant),
         };
         tokens.extend(quote!{
             impl #impl_generics #oapi::oapi::ToSchema for #ident #ty_generics #where_clause {
@@ -122,10 +232,42 @@ impl TryToTokens for ToSchema<'_> {
                 }
             }
         });
+
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
         Ok(())
     }
 }
 
+/// Default separator between a generic component's symbol and its sanitized type arguments,
+/// e.g. `Page_Of_my_app_User` for `Page<my_app::User>` (argument paths are kept, with `::`
+/// turned into `_`).
+const DEFAULT_GENERIC_NAME_SEPARATOR: &str = "_Of_";
+
+/// Attaches the OpenAPI `xml` object built from `xml` to `schema` tokens via the `.xml(...)`
+/// builder call. Used both for a container's own `#[salvo(schema(xml(...)))]` and, from
+/// `struct_schemas`, for each field's `xml` attribute.
+pub(crate) fn wrap_schema_xml(schema: TokenStream, xml: &Option<XmlAttr>) -> TokenStream {
+    match xml {
+        Some(xml) => quote! { (#schema).xml(#xml) },
+        None => schema,
+    }
+}
+
+/// Inserts `schema` into `components.schemas` under `symbol` and returns a `$ref` pointing at
+/// it. Besides the top-level [`ToSchema`] impl, `EnumSchema` reuses this for tagged enums:
+/// each variant of a `oneOf`/`discriminator` schema is registered as its own named component
+/// through this same insert-and-ref path before being referenced from the `mapping`.
+pub(crate) fn insert_schema_component(symbol: &TokenStream, schema: &TokenStream) -> TokenStream {
+    let oapi = crate::oapi_crate();
+    quote! {
+        let schema = #schema;
+        components.schemas.insert(#symbol, schema.into());
+        #oapi::oapi::RefOr::Ref(#oapi::oapi::Ref::new(format!("#/components/schemas/{}", #symbol)))
+    }
+}
+
 #[derive(Debug)]
 enum SchemaVariant<'a> {
     Named(NamedStructSchema<'a>),
@@ -149,6 +291,8 @@ impl<'a> SchemaVariant<'a> {
 
                     let symbol = pop_feature_as_inner!(unnamed_features => Feature::Symbol(_v));
                     let inline = pop_feature_as_inner!(unnamed_features => Feature::Inline(_v));
+                    let xml = pop_feature_as_inner!(unnamed_features => Feature::Xml(_v));
+                    let name_separator = pop_feature_as_inner!(unnamed_features => Feature::NameSeparator(_v));
                     Ok(Self::Unnamed(UnnamedStructSchema {
                         struct_name: Cow::Owned(ident.to_string()),
                         attributes,
@@ -156,6 +300,8 @@ impl<'a> SchemaVariant<'a> {
                         fields: unnamed,
                         symbol,
                         inline,
+                        xml,
+                        name_separator,
                     }))
                 }
                 Fields::Named(fields) => {
@@ -163,6 +309,8 @@ impl<'a> SchemaVariant<'a> {
                     let mut named_features = attributes.parse_features::<NamedFieldStructFeatures>()?.into_inner();
                     let symbol = pop_feature_as_inner!(named_features => Feature::Symbol(_v));
                     let inline = pop_feature_as_inner!(named_features => Feature::Inline(_v));
+                    let xml = pop_feature_as_inner!(named_features => Feature::Xml(_v));
+                    let name_separator = pop_feature_as_inner!(named_features => Feature::NameSeparator(_v));
 
                     Ok(Self::Named(NamedStructSchema {
                         struct_name: Cow::Owned(ident.to_string()),
@@ -173,6 +321,8 @@ impl<'a> SchemaVariant<'a> {
                         generics: Some(generics),
                         symbol,
                         inline,
+                        xml,
+                        name_separator,
                     }))
                 }
                 Fields::Unit => Ok(Self::Unit(UnitStructVariant)),
@@ -198,6 +348,17 @@ impl<'a> SchemaVariant<'a> {
             _ => &None,
         }
     }
+    /// Container-level `#[salvo(schema(xml(...)))]`, e.g. `xml(name = "...")` on the struct
+    /// or enum itself, as opposed to the per-field attribute threaded through
+    /// `NamedStructSchema`/`UnnamedStructSchema`.
+    fn xml(&self) -> &Option<XmlAttr> {
+        match self {
+            Self::Enum(schema) => &schema.xml,
+            Self::Named(schema) => &schema.xml,
+            Self::Unnamed(schema) => &schema.xml,
+            _ => &None,
+        }
+    }
     fn inline(&self) -> &Option<Inline> {
         match self {
             Self::Enum(schema) => &schema.inline,
@@ -222,6 +383,15 @@ impl<'a> SchemaVariant<'a> {
             _ => None,
         }
     }
+    /// `#[salvo(schema(name_separator = "."))]` override of [`DEFAULT_GENERIC_NAME_SEPARATOR`].
+    fn pop_generic_name_separator(&mut self) -> Option<NameSeparator> {
+        match self {
+            Self::Enum(schema) => schema.pop_generic_name_separator(),
+            Self::Named(schema) => schema.pop_generic_name_separator(),
+            Self::Unnamed(schema) => schema.pop_generic_name_separator(),
+            _ => None,
+        }
+    }
 }
 
 impl TryToTokens for SchemaVariant<'_> {
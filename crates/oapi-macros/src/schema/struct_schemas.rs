@@ -0,0 +1,179 @@
+use std::borrow::Cow;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Field, Generics, Token};
+
+use super::feature::{FieldFeatures, FromAttributes};
+use super::{wrap_schema_xml, DiagResultExt, XmlAttr};
+use crate::feature::{
+    pop_feature_as_inner, Bound, Feature, FeaturesExt, Inline, IntoInner, NameSeparator, RenameAll, SkipBound, Symbol,
+};
+use crate::{DiagResult, TryToTokens};
+
+fn pop_skip_bound(features: &mut Vec<Feature>) -> Option<SkipBound> {
+    let index = features.iter().position(|feature| matches!(feature, Feature::SkipBound(_)))?;
+    match features.remove(index) {
+        Feature::SkipBound(skip_bound) => Some(skip_bound),
+        _ => unreachable!(),
+    }
+}
+
+fn pop_bound(features: &mut Vec<Feature>) -> Option<Bound> {
+    let index = features.iter().position(|feature| matches!(feature, Feature::Bound(_)))?;
+    match features.remove(index) {
+        Feature::Bound(bound) => Some(bound),
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct NamedStructSchema<'a> {
+    pub(super) struct_name: Cow<'a, str>,
+    pub(super) attributes: &'a [Attribute],
+    pub(super) rename_all: Option<RenameAll>,
+    pub(super) features: Vec<Feature>,
+    pub(super) fields: &'a Punctuated<Field, Token![,]>,
+    pub(super) generics: Option<&'a Generics>,
+    pub(super) symbol: Option<Symbol>,
+    pub(super) inline: Option<Inline>,
+    pub(super) xml: Option<XmlAttr>,
+    pub(super) name_separator: Option<NameSeparator>,
+}
+
+impl NamedStructSchema<'_> {
+    pub(super) fn pop_skip_bound(&mut self) -> Option<SkipBound> {
+        pop_skip_bound(&mut self.features)
+    }
+
+    pub(super) fn pop_bound(&mut self) -> Option<Bound> {
+        pop_bound(&mut self.features)
+    }
+
+    pub(super) fn pop_generic_name_separator(&mut self) -> Option<NameSeparator> {
+        self.name_separator.take()
+    }
+}
+
+impl TryToTokens for NamedStructSchema<'_> {
+    fn try_to_tokens(&self, tokens: &mut TokenStream) -> DiagResult<()> {
+        let oapi = crate::oapi_crate();
+        let mut errors = None;
+        let mut properties = Vec::new();
+        let mut required = Vec::new();
+
+        for field in self.fields {
+            let Some(ident) = field.ident.as_ref() else {
+                continue;
+            };
+            let Some(field_features) = field
+                .attrs
+                .parse_features::<FieldFeatures>()
+                .accumulate(&mut errors)
+            else {
+                continue;
+            };
+            let mut field_features = field_features.into_inner();
+            let field_xml = pop_feature_as_inner!(field_features => Feature::Xml(_v));
+
+            let name = self
+                .rename_all
+                .as_ref()
+                .map(|rename_all| rename_all.rename(&ident.to_string()))
+                .unwrap_or_else(|| ident.to_string());
+            // `<#ty as ToSchema>::to_schema(components)` is the one opaque call this loop has for
+            // the field's value — for a `Vec<T>` field that's `Vec<T>`'s own compiled ToSchema
+            // impl, which this site can't reach into. So `field_xml`'s `wrapped`/item-name split
+            // has to be encoded inside `XmlAttr` itself (carried through by its `ToTokens`); this
+            // call site only has one place to attach it, same as the container-level XML.
+            let ty = &field.ty;
+            let schema = wrap_schema_xml(quote! { <#ty as #oapi::oapi::ToSchema>::to_schema(components) }, &field_xml);
+            properties.push(quote! {
+                (#name, #schema)
+            });
+            required.push(name);
+        }
+
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+
+        tokens.extend(quote! {
+            {
+                let mut object = #oapi::oapi::schema::Object::new();
+                #(
+                    let (name, schema) = #properties;
+                    object = object.property(name, schema);
+                )*
+                #(object = object.required(#required);)*
+                object
+            }
+        });
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct UnnamedStructSchema<'a> {
+    pub(super) struct_name: Cow<'a, str>,
+    pub(super) attributes: &'a [Attribute],
+    pub(super) features: Vec<Feature>,
+    pub(super) fields: &'a Punctuated<Field, Token![,]>,
+    pub(super) symbol: Option<Symbol>,
+    pub(super) inline: Option<Inline>,
+    pub(super) xml: Option<XmlAttr>,
+    pub(super) name_separator: Option<NameSeparator>,
+}
+
+impl UnnamedStructSchema<'_> {
+    pub(super) fn pop_skip_bound(&mut self) -> Option<SkipBound> {
+        pop_skip_bound(&mut self.features)
+    }
+
+    pub(super) fn pop_bound(&mut self) -> Option<Bound> {
+        pop_bound(&mut self.features)
+    }
+
+    pub(super) fn pop_generic_name_separator(&mut self) -> Option<NameSeparator> {
+        self.name_separator.take()
+    }
+}
+
+impl TryToTokens for UnnamedStructSchema<'_> {
+    fn try_to_tokens(&self, tokens: &mut TokenStream) -> DiagResult<()> {
+        let oapi = crate::oapi_crate();
+        let mut errors = None;
+        let mut items = Vec::new();
+
+        for field in self.fields {
+            let Some(field_features) = field
+                .attrs
+                .parse_features::<FieldFeatures>()
+                .accumulate(&mut errors)
+            else {
+                continue;
+            };
+            let mut field_features = field_features.into_inner();
+            let field_xml = pop_feature_as_inner!(field_features => Feature::Xml(_v));
+
+            let ty = &field.ty;
+            items.push(wrap_schema_xml(quote! { <#ty as #oapi::oapi::ToSchema>::to_schema(components) }, &field_xml));
+        }
+
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+
+        tokens.extend(if items.len() == 1 {
+            let item = &items[0];
+            quote! { #item }
+        } else {
+            quote! {
+                #oapi::oapi::schema::Array::new()
+                    #(.item(#items))*
+            }
+        });
+        Ok(())
+    }
+}